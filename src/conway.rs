@@ -1,7 +1,12 @@
 use std::fmt;
+use std::mem;
+
+use raylib::prelude::Color;
+
+use crate::framebuffer::Framebuffer;
 
 #[repr(u8)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Cell {
     Alive = 1,
     Dead = 0,
@@ -10,8 +15,8 @@ pub enum Cell {
 impl fmt::Display for Cell {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let symbol = match self {
-            Cell::Alive => "0",
-            Cell::Dead => "X",
+            Cell::Alive => "#",
+            Cell::Dead => ".",
         };
 
         write!(f, "{}", symbol)
@@ -20,6 +25,9 @@ impl fmt::Display for Cell {
 
 pub struct Matrix {
     flat_matrix: Vec<Cell>,
+    next_flat_matrix: Vec<Cell>,
+    neighbor_counts: Vec<u8>,
+    changed_cells: Vec<usize>,
     width: usize,
     height: usize,
 }
@@ -28,6 +36,9 @@ impl Matrix {
     pub fn new(width: usize, height: usize) -> Self {
         Matrix {
             flat_matrix: vec![Cell::Dead; width * height],
+            next_flat_matrix: Vec::new(),
+            neighbor_counts: vec![0; width * height],
+            changed_cells: Vec::new(),
             width,
             height,
         }
@@ -35,75 +46,188 @@ impl Matrix {
 
     pub fn set_cell(&mut self, x: usize, y: usize, cell: Cell) {
         let idx = self.transform_2d_coordinate_into_flat_idx(x, y);
+        let previous_cell = self.flat_matrix[idx];
         self.flat_matrix[idx] = cell;
+
+        if previous_cell != cell {
+            self.update_neighbor_counts(x, y, cell);
+        }
     }
 
-    pub fn get_cell(&mut self, x: usize, y: usize) -> Cell {
+    pub fn get_cell(&self, x: usize, y: usize) -> Cell {
         let idx = self.transform_2d_coordinate_into_flat_idx(x, y);
         self.flat_matrix[idx]
     }
 
+    // Reference implementation for the tests: rescans every neighborhood and
+    // never touches the cached neighbor counts, which are only rebuilt once at
+    // the end so the result can keep advancing with advance_generation.
+    #[cfg(test)]
     pub fn calculate_next_generation(&self) -> Self {
         let mut next_generation = Matrix::new(self.width, self.height);
 
         let mut x: usize;
         let mut y: usize;
-        let mut alive_neighbors: u8;
         let mut current_cell_and_alive_neighbors: (&Cell, u8);
         let mut next_cell: Cell;
 
-        let mut starting_column: usize;
-        let mut ending_column: usize;
-
-        let mut starting_row: usize;
-        let mut ending_row: usize;
-
         for (cell_idx, cell) in self.flat_matrix.iter().enumerate() {
-            alive_neighbors = 0;
             x = cell_idx % self.width;
             y = (cell_idx - x) / self.width;
 
-            starting_column = if x > 0 { x - 1 } else { 0 };
-            ending_column = if x < self.width - 1 {
-                x + 1
-            } else {
-                self.width - 1
-            };
+            current_cell_and_alive_neighbors = (cell, self.count_alive_neighbors(x, y));
 
-            starting_row = if y > 0 { y - 1 } else { 0 };
-            ending_row = if y < self.height - 1 {
-                y + 1
-            } else {
-                self.height - 1
+            next_cell = match current_cell_and_alive_neighbors {
+                (Cell::Alive, 2) => Cell::Alive,
+                (Cell::Alive, 3) => Cell::Alive,
+                (Cell::Dead, 3) => Cell::Alive,
+                (_, _) => Cell::Dead,
             };
 
-            for row in starting_row..=ending_row {
-                for column in starting_column..=ending_column {
-                    if let Cell::Alive =
-                        self.flat_matrix[self.transform_2d_coordinate_into_flat_idx(column, row)]
-                    {
-                        alive_neighbors += 1;
-                    }
-                }
-            }
+            next_generation.flat_matrix[cell_idx] = next_cell;
+        }
 
-            if let Cell::Alive = cell {
-                alive_neighbors -= 1;
-            }
+        next_generation.recount_neighbors();
+        next_generation
+    }
 
-            current_cell_and_alive_neighbors = (cell, alive_neighbors);
+    // Same rules as calculate_next_generation, but reads the cached neighbor
+    // counts instead of rescanning every neighborhood and reuses the back
+    // buffer instead of allocating a new Matrix. Only the counts around cells
+    // that actually changed are updated afterwards.
+    pub fn advance_generation(&mut self) {
+        self.next_flat_matrix
+            .resize(self.flat_matrix.len(), Cell::Dead);
+        self.changed_cells.clear();
 
-            next_cell = match current_cell_and_alive_neighbors {
+        for (cell_idx, (cell, next_cell)) in self
+            .flat_matrix
+            .iter()
+            .zip(self.next_flat_matrix.iter_mut())
+            .enumerate()
+        {
+            *next_cell = match (cell, self.neighbor_counts[cell_idx]) {
                 (Cell::Alive, 2) => Cell::Alive,
                 (Cell::Alive, 3) => Cell::Alive,
                 (Cell::Dead, 3) => Cell::Alive,
                 (_, _) => Cell::Dead,
             };
 
-            next_generation.set_cell(x, y, next_cell);
+            if *next_cell != *cell {
+                self.changed_cells.push(cell_idx);
+            }
         }
 
-        next_generation
+        mem::swap(&mut self.flat_matrix, &mut self.next_flat_matrix);
+
+        let changed_cells = mem::take(&mut self.changed_cells);
+        for &cell_idx in &changed_cells {
+            let x = cell_idx % self.width;
+            let y = cell_idx / self.width;
+            self.update_neighbor_counts(x, y, self.flat_matrix[cell_idx]);
+        }
+        self.changed_cells = changed_cells;
+    }
+
+    pub fn render_to_framebuffer(
+        &self,
+        framebuffer: &mut Framebuffer,
+        cell_size: usize,
+        alive_color: Color,
+        dead_color: Color,
+    ) {
+        let offset_x = (framebuffer.width() - (self.width * cell_size) as i32) / 2;
+        let offset_y = (framebuffer.height() - (self.height * cell_size) as i32) / 2;
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                framebuffer.set_foreground_color(match self.get_cell(x, y) {
+                    Cell::Alive => alive_color,
+                    Cell::Dead => dead_color,
+                });
+
+                for px in x * cell_size..(x + 1) * cell_size {
+                    for py in y * cell_size..(y + 1) * cell_size {
+                        framebuffer.set_pixel(offset_x + px as i32, offset_y + py as i32);
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    fn count_alive_neighbors(&self, x: usize, y: usize) -> u8 {
+        let mut alive_neighbors: u8 = 0;
+
+        let starting_column = if x > 0 { x - 1 } else { 0 };
+        let ending_column = if x < self.width - 1 {
+            x + 1
+        } else {
+            self.width - 1
+        };
+
+        let starting_row = if y > 0 { y - 1 } else { 0 };
+        let ending_row = if y < self.height - 1 {
+            y + 1
+        } else {
+            self.height - 1
+        };
+
+        for row in starting_row..=ending_row {
+            for column in starting_column..=ending_column {
+                if let Cell::Alive =
+                    self.flat_matrix[self.transform_2d_coordinate_into_flat_idx(column, row)]
+                {
+                    alive_neighbors += 1;
+                }
+            }
+        }
+
+        if let Cell::Alive = self.flat_matrix[self.transform_2d_coordinate_into_flat_idx(x, y)] {
+            alive_neighbors -= 1;
+        }
+
+        alive_neighbors
+    }
+
+    #[cfg(test)]
+    fn recount_neighbors(&mut self) {
+        for cell_idx in 0..self.flat_matrix.len() {
+            self.neighbor_counts[cell_idx] =
+                self.count_alive_neighbors(cell_idx % self.width, cell_idx / self.width);
+        }
+    }
+
+    fn update_neighbor_counts(&mut self, x: usize, y: usize, new_cell: Cell) {
+        let starting_column = if x > 0 { x - 1 } else { 0 };
+        let ending_column = if x < self.width - 1 {
+            x + 1
+        } else {
+            self.width - 1
+        };
+
+        let starting_row = if y > 0 { y - 1 } else { 0 };
+        let ending_row = if y < self.height - 1 {
+            y + 1
+        } else {
+            self.height - 1
+        };
+
+        let mut idx: usize;
+
+        for row in starting_row..=ending_row {
+            for column in starting_column..=ending_column {
+                if row == y && column == x {
+                    continue;
+                }
+
+                idx = self.transform_2d_coordinate_into_flat_idx(column, row);
+                match new_cell {
+                    Cell::Alive => self.neighbor_counts[idx] += 1,
+                    Cell::Dead => self.neighbor_counts[idx] -= 1,
+                }
+            }
+        }
     }
 
     fn transform_2d_coordinate_into_flat_idx(&self, x: usize, y: usize) -> usize {
@@ -113,13 +237,163 @@ impl Matrix {
 
 impl fmt::Display for Matrix {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for (cell_idx, cell) in self.flat_matrix.iter().enumerate() {
-            if cell_idx % self.width == 0 {
-                writeln!(f);
+        if self.width == 0 {
+            return Ok(());
+        }
+
+        for row in self.flat_matrix.chunks(self.width) {
+            for (column_idx, cell) in row.iter().enumerate() {
+                if column_idx > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{cell}")?;
             }
-            write!(f, "{cell} ");
+            writeln!(f)?;
         }
-        writeln!(f);
+
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Instant;
+
+    #[test]
+    fn blinker_text_output_alternates_between_phases() {
+        let mut m = Matrix::new(5, 5);
+        for x in 1..=3 {
+            m.set_cell(x, 2, Cell::Alive);
+        }
+
+        assert_eq!(
+            format!("{m}"),
+            ". . . . .\n\
+             . . . . .\n\
+             . # # # .\n\
+             . . . . .\n\
+             . . . . .\n"
+        );
+
+        m = m.calculate_next_generation();
+
+        assert_eq!(
+            format!("{m}"),
+            ". . . . .\n\
+             . . # . .\n\
+             . . # . .\n\
+             . . # . .\n\
+             . . . . .\n"
+        );
+    }
+
+    fn rgba(color: Color) -> (u8, u8, u8, u8) {
+        (color.r, color.g, color.b, color.a)
+    }
+
+    #[test]
+    fn render_to_framebuffer_paints_centered_scaled_cells() {
+        let mut m = Matrix::new(2, 2);
+        m.set_cell(1, 0, Cell::Alive);
+        m.set_cell(0, 1, Cell::Alive);
+
+        let mut framebuffer = Framebuffer::new(6, 6, Color::BLACK);
+        m.render_to_framebuffer(&mut framebuffer, 2, Color::GOLD, Color::DARKBLUE);
+
+        // The 4x4 grid sits one pixel in from each edge of the 6x6 framebuffer.
+        for y in 0..6 {
+            for x in 0..6 {
+                let expected = match (x, y) {
+                    (1..=4, 1..=4) => {
+                        match m.get_cell((x as usize - 1) / 2, (y as usize - 1) / 2) {
+                            Cell::Alive => Color::GOLD,
+                            Cell::Dead => Color::DARKBLUE,
+                        }
+                    }
+                    _ => Color::BLACK,
+                };
+
+                assert_eq!(
+                    rgba(framebuffer.color_buffer.get_color(x, y)),
+                    rgba(expected),
+                    "pixel ({x}, {y})"
+                );
+            }
+        }
+    }
+
+    fn r_pentomino(width: usize, height: usize) -> Matrix {
+        let mut m = Matrix::new(width, height);
+        let (cx, cy) = (width / 2, height / 2);
+        for (x, y) in [
+            (cx, cy - 1),
+            (cx + 1, cy - 1),
+            (cx - 1, cy),
+            (cx, cy),
+            (cx, cy + 1),
+        ] {
+            m.set_cell(x, y, Cell::Alive);
+        }
+        m
+    }
+
+    fn pseudo_random_grid(width: usize, height: usize) -> Matrix {
+        let mut m = Matrix::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                if (x * 31 + y * 17 + x * y) % 7 < 3 {
+                    m.set_cell(x, y, Cell::Alive);
+                }
+            }
+        }
+        m
+    }
+
+    #[test]
+    fn advance_generation_matches_naive_path_for_r_pentomino() {
+        let mut naive = r_pentomino(64, 64);
+        let mut cached = r_pentomino(64, 64);
+
+        for generation in 1..=100 {
+            naive = naive.calculate_next_generation();
+            cached.advance_generation();
+
+            assert_eq!(
+                naive.flat_matrix, cached.flat_matrix,
+                "cells diverged at generation {generation}"
+            );
+            assert_eq!(
+                naive.neighbor_counts, cached.neighbor_counts,
+                "neighbor counts diverged at generation {generation}"
+            );
+        }
+    }
+
+    // Run with `cargo test --release -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn benchmark_512x512_generations() {
+        const GENERATIONS: usize = 100;
+
+        let mut naive = pseudo_random_grid(512, 512);
+        let start = Instant::now();
+        for _ in 0..GENERATIONS {
+            naive = naive.calculate_next_generation();
+        }
+        let naive_elapsed = start.elapsed();
+
+        let mut cached = pseudo_random_grid(512, 512);
+        let start = Instant::now();
+        for _ in 0..GENERATIONS {
+            cached.advance_generation();
+        }
+        let cached_elapsed = start.elapsed();
+
+        println!(
+            "512x512, {GENERATIONS} generations: naive {naive_elapsed:?}, cached {cached_elapsed:?}"
+        );
+        assert_eq!(naive.flat_matrix, cached.flat_matrix);
+    }
+}
@@ -0,0 +1,214 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+pub const MAX_BRIGHTNESS: i32 = 100;
+pub const MIN_GAMMA: f32 = 0.5;
+pub const MAX_GAMMA: f32 = 2.5;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DisplaySettings {
+    pub brightness: i32,
+    pub gamma: f32,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        DisplaySettings {
+            brightness: 0,
+            gamma: 1.0,
+        }
+    }
+}
+
+impl DisplaySettings {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(contents) => DisplaySettings::parse(&contents),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(DisplaySettings::default()),
+            Err(error) => Err(format!("could not read `{}`: {error}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let mut settings = DisplaySettings::default();
+
+        for line in contents.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("invalid line `{line}`, expected KEY = VALUE"))?;
+
+            match key.trim() {
+                "brightness" => settings.brightness = parse_brightness(value.trim())?,
+                "gamma" => settings.gamma = parse_gamma(value.trim())?,
+                key => return Err(format!("unknown setting `{key}`")),
+            }
+        }
+
+        Ok(settings)
+    }
+
+    pub fn adjust_brightness(&mut self, delta: i32) {
+        self.brightness = (self.brightness + delta).clamp(-MAX_BRIGHTNESS, MAX_BRIGHTNESS);
+    }
+
+    // Rounded to one decimal so repeated steps land back on exactly 1.0 and
+    // match what gets written to the settings file.
+    pub fn adjust_gamma(&mut self, delta: f32) {
+        let gamma = ((self.gamma + delta) * 10.0).round() / 10.0;
+        self.gamma = gamma.clamp(MIN_GAMMA, MAX_GAMMA);
+    }
+
+    pub fn is_neutral(&self) -> bool {
+        self.brightness == 0 && self.gamma == 1.0
+    }
+
+    // Brightness is an additive offset on every channel, like raylib's
+    // ColorBrightness; gamma is then applied as out = in^(1 / gamma).
+    pub fn lookup_table(&self) -> [u8; 256] {
+        let mut table = [0; 256];
+
+        for (value, entry) in table.iter_mut().enumerate() {
+            let brightened = (value as i32 + self.brightness).clamp(0, 255) as f32 / 255.0;
+            *entry = (brightened.powf(1.0 / self.gamma) * 255.0).round() as u8;
+        }
+
+        table
+    }
+}
+
+impl fmt::Display for DisplaySettings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "brightness = {}", self.brightness)?;
+        writeln!(f, "gamma = {:.1}", self.gamma)
+    }
+}
+
+pub fn parse_brightness(value: &str) -> Result<i32, String> {
+    let brightness: i32 = value
+        .parse()
+        .map_err(|_| format!("invalid brightness `{value}`, expected an integer"))?;
+
+    Ok(brightness.clamp(-MAX_BRIGHTNESS, MAX_BRIGHTNESS))
+}
+
+pub fn parse_gamma(value: &str) -> Result<f32, String> {
+    let gamma: f32 = value
+        .parse()
+        .ok()
+        .filter(|gamma: &f32| gamma.is_finite())
+        .ok_or_else(|| format!("invalid gamma `{value}`, expected a number"))?;
+
+    Ok(gamma.clamp(MIN_GAMMA, MAX_GAMMA))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_settings_file() {
+        assert_eq!(
+            DisplaySettings::parse("# display\nbrightness = 30\n\ngamma = 1.2\n"),
+            Ok(DisplaySettings {
+                brightness: 30,
+                gamma: 1.2,
+            })
+        );
+    }
+
+    #[test]
+    fn missing_settings_keep_defaults() {
+        assert_eq!(DisplaySettings::parse(""), Ok(DisplaySettings::default()));
+    }
+
+    #[test]
+    fn rejects_unknown_or_malformed_settings() {
+        assert!(DisplaySettings::parse("contrast = 2").is_err());
+        assert!(DisplaySettings::parse("brightness 30").is_err());
+        assert!(DisplaySettings::parse("gamma = dark").is_err());
+    }
+
+    #[test]
+    fn settings_round_trip_through_text() {
+        let settings = DisplaySettings {
+            brightness: -40,
+            gamma: 1.8,
+        };
+        assert_eq!(DisplaySettings::parse(&settings.to_string()), Ok(settings));
+    }
+
+    #[test]
+    fn clamps_brightness_and_gamma() {
+        assert_eq!(parse_brightness("500"), Ok(MAX_BRIGHTNESS));
+        assert_eq!(parse_brightness("-500"), Ok(-MAX_BRIGHTNESS));
+        assert_eq!(parse_gamma("10"), Ok(MAX_GAMMA));
+        assert_eq!(parse_gamma("0"), Ok(MIN_GAMMA));
+        assert!(parse_gamma("NaN").is_err());
+    }
+
+    #[test]
+    fn adjustments_clamp_and_return_to_neutral() {
+        let mut settings = DisplaySettings::default();
+
+        for _ in 0..3 {
+            settings.adjust_gamma(0.1);
+        }
+        for _ in 0..3 {
+            settings.adjust_gamma(-0.1);
+        }
+        settings.adjust_brightness(10);
+        settings.adjust_brightness(-10);
+        assert!(settings.is_neutral());
+
+        settings.adjust_brightness(1000);
+        settings.adjust_gamma(100.0);
+        assert_eq!(
+            settings,
+            DisplaySettings {
+                brightness: MAX_BRIGHTNESS,
+                gamma: MAX_GAMMA,
+            }
+        );
+    }
+
+    #[test]
+    fn neutral_lookup_table_is_identity() {
+        let table = DisplaySettings::default().lookup_table();
+        assert!(
+            table
+                .iter()
+                .enumerate()
+                .all(|(value, &entry)| entry as usize == value)
+        );
+    }
+
+    #[test]
+    fn lookup_table_applies_brightness_then_gamma() {
+        let brightened = DisplaySettings {
+            brightness: 20,
+            gamma: 1.0,
+        }
+        .lookup_table();
+        assert_eq!(
+            (brightened[0], brightened[100], brightened[250]),
+            (20, 120, 255)
+        );
+
+        let gamma = DisplaySettings {
+            brightness: 0,
+            gamma: 2.0,
+        }
+        .lookup_table();
+        assert_eq!((gamma[0], gamma[64], gamma[255]), (0, 128, 255));
+    }
+}
@@ -49,7 +49,44 @@ impl Framebuffer {
             .draw_line(xi, yi, xf, yf, self.foreground_color);
     }
 
+    pub fn adjusted_pixels(&self, lookup_table: &[u8; 256]) -> Vec<u8> {
+        self.color_buffer
+            .get_image_data()
+            .iter()
+            .flat_map(|color| {
+                [
+                    lookup_table[color.r as usize],
+                    lookup_table[color.g as usize],
+                    lookup_table[color.b as usize],
+                    color.a,
+                ]
+            })
+            .collect()
+    }
+
     pub fn render_to_png(&self, filename: &str) {
         self.color_buffer.export_image(filename);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adjusted_pixels_map_color_channels_and_keep_alpha() {
+        let mut framebuffer = Framebuffer::new(2, 1, Color::new(10, 20, 30, 40));
+        framebuffer.set_foreground_color(Color::new(200, 100, 0, 255));
+        framebuffer.set_pixel(1, 0);
+
+        let mut lookup_table = [0; 256];
+        for (value, entry) in lookup_table.iter_mut().enumerate() {
+            *entry = (value as u8).saturating_add(5);
+        }
+
+        assert_eq!(
+            framebuffer.adjusted_pixels(&lookup_table),
+            vec![15, 25, 35, 40, 205, 105, 5, 255]
+        );
+    }
+}
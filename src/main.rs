@@ -1,43 +1,103 @@
 #![allow(warnings)]
 
 mod conway;
+mod display;
 mod framebuffer;
+mod window;
 
 use rand::Rng;
 use raylib::prelude::*;
 
+use std::env;
+use std::path::Path;
+use std::process;
 use std::thread;
 use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
 
 use conway::Cell;
 use conway::Matrix;
+use display::DisplaySettings;
 use framebuffer::Framebuffer;
+use window::WindowConfig;
+use window::WindowMode;
+use window::clamp_to_monitor;
 
-const MATRIX_WIDTH: usize = WINDOW_WIDTH as usize;
-const MATRIX_HEIGHT: usize = WINDOW_HEIGHT as usize;
+const DEFAULT_WINDOW_WIDTH: i32 = 1900;
+const DEFAULT_WINDOW_HEIGHT: i32 = 1000;
 
-const WINDOW_WIDTH: i32 = 1900;
-const WINDOW_HEIGHT: i32 = 1000;
-
-const FREMEBUFFER_WIDTH: i32 = WINDOW_WIDTH;
-const FRAMEBUFFER_HEIGHT: i32 = WINDOW_HEIGHT;
 const MATRIX_CELL_SCALLING_FACTOR: usize = 1;
 
+const BRIGHTNESS_STEP: i32 = 10;
+const GAMMA_STEP: f32 = 0.1;
+
+const DISPLAY_SETTINGS_PATH: &str = "display.cfg";
+
 fn main() {
-    game_loop();
+    let window_config = WindowConfig::from_args(env::args()).unwrap_or_else(|error| {
+        eprintln!("{error}");
+        eprintln!(
+            "usage: game_of_life [--windowed | --borderless | --fullscreen] [--resolution WIDTHxHEIGHT] [--brightness N] [--gamma G]"
+        );
+        process::exit(2);
+    });
+
+    let mut display_settings = DisplaySettings::load(Path::new(DISPLAY_SETTINGS_PATH))
+        .unwrap_or_else(|error| {
+            eprintln!("Ignoring display settings: {error}");
+            DisplaySettings::default()
+        });
+
+    if let Some(brightness) = window_config.brightness {
+        display_settings.brightness = brightness;
+    }
+
+    if let Some(gamma) = window_config.gamma {
+        display_settings.gamma = gamma;
+    }
+
+    game_loop(window_config, display_settings);
 }
 
-fn game_loop() {
-    let (mut handle, raylib_thread) = raylib::init()
-        .undecorated()
-        .size(WINDOW_WIDTH, WINDOW_HEIGHT)
+fn game_loop(window_config: WindowConfig, mut display_settings: DisplaySettings) {
+    let (requested_width, requested_height) =
+        window_config.requested_size(DEFAULT_WINDOW_WIDTH, DEFAULT_WINDOW_HEIGHT);
+
+    let mut builder = raylib::init();
+    builder
+        .size(requested_width, requested_height)
         .title("raylib")
-        .log_level(TraceLogLevel::LOG_WARNING)
-        .build();
+        .log_level(TraceLogLevel::LOG_WARNING);
+
+    match window_config.mode {
+        WindowMode::Windowed => {}
+        WindowMode::Borderless => {
+            builder.undecorated();
+        }
+        WindowMode::Fullscreen => {
+            builder.fullscreen();
+        }
+    }
 
-    let mut framebuffer = Framebuffer::new(FREMEBUFFER_WIDTH, FRAMEBUFFER_HEIGHT, Color::WHITE);
+    let (mut handle, raylib_thread) = builder.build();
 
-    let mut game_of_life = Matrix::new(MATRIX_WIDTH, MATRIX_HEIGHT);
+    match window_config.mode {
+        WindowMode::Fullscreen => warn_about_fullscreen_resolution(window_config.resolution),
+        WindowMode::Windowed | WindowMode::Borderless => {
+            cap_window_to_monitor(&mut handle, (requested_width, requested_height));
+        }
+    }
+
+    let screen_width = handle.get_render_width();
+    let screen_height = handle.get_render_height();
+
+    let matrix_width = screen_width as usize / MATRIX_CELL_SCALLING_FACTOR;
+    let matrix_height = screen_height as usize / MATRIX_CELL_SCALLING_FACTOR;
+
+    let mut framebuffer = Framebuffer::new(screen_width, screen_height, Color::WHITE);
+
+    let mut game_of_life = Matrix::new(matrix_width, matrix_height);
 
     //Initial values
     //for i in 20..=30 {
@@ -46,8 +106,8 @@ fn game_loop() {
     //
     let mut rng = rand::rng();
 
-    for i in 0..MATRIX_WIDTH {
-        for j in 0..MATRIX_HEIGHT {
+    for i in 0..matrix_width {
+        for j in 0..matrix_height {
             let random_number = rng.random_range(0..100);
             game_of_life.set_cell(
                 i,
@@ -62,53 +122,75 @@ fn game_loop() {
     }
 
     while !&handle.window_should_close() {
-        let mut current_cell: Cell;
-        let mut current_color: Color;
-
-        let mut fx: usize;
-        let mut fy: usize;
-
-        for i in 0..MATRIX_WIDTH {
-            for j in 0..MATRIX_HEIGHT {
-                current_cell = game_of_life.get_cell(i, j);
-
-                current_color = match current_cell {
-                    Cell::Alive => Color::GOLD,
-                    Cell::Dead => Color::DARKBLUE,
-                };
-
-                framebuffer.set_foreground_color(current_color);
-
-                fx = i * MATRIX_CELL_SCALLING_FACTOR;
-                fy = j * MATRIX_CELL_SCALLING_FACTOR;
-
-                for px in fx..fx + MATRIX_CELL_SCALLING_FACTOR {
-                    for py in fy..fy + MATRIX_CELL_SCALLING_FACTOR {
-                        framebuffer.set_pixel(
-                            (framebuffer.width()
-                                - MATRIX_WIDTH as i32 * MATRIX_CELL_SCALLING_FACTOR as i32)
-                                / 2
-                                + px as i32,
-                            (framebuffer.height()
-                                - MATRIX_HEIGHT as i32 * MATRIX_CELL_SCALLING_FACTOR as i32)
-                                / 2
-                                + py as i32,
-                        );
-                    }
-                }
+        if handle.is_key_pressed(KeyboardKey::KEY_F11) {
+            handle.toggle_fullscreen();
+
+            // Starting with --fullscreen leaves the window at the monitor's
+            // size, which does not fit once decorations come back.
+            if !handle.is_window_fullscreen() {
+                cap_window_to_monitor(
+                    &mut handle,
+                    window_config.windowed_size(DEFAULT_WINDOW_WIDTH, DEFAULT_WINDOW_HEIGHT),
+                );
             }
         }
 
+        // The grid keeps its size and is re-centered inside the new framebuffer.
+        let render_width = handle.get_render_width();
+        let render_height = handle.get_render_height();
+        if (render_width, render_height) != (framebuffer.width(), framebuffer.height()) {
+            framebuffer = Framebuffer::new(render_width, render_height, Color::WHITE);
+        }
+
+        let previous_display_settings = display_settings;
+
+        if handle.is_key_pressed(KeyboardKey::KEY_EQUAL) {
+            display_settings.adjust_brightness(BRIGHTNESS_STEP);
+        }
+
+        if handle.is_key_pressed(KeyboardKey::KEY_MINUS) {
+            display_settings.adjust_brightness(-BRIGHTNESS_STEP);
+        }
+
+        if handle.is_key_pressed(KeyboardKey::KEY_RIGHT_BRACKET) {
+            display_settings.adjust_gamma(GAMMA_STEP);
+        }
+
+        if handle.is_key_pressed(KeyboardKey::KEY_LEFT_BRACKET) {
+            display_settings.adjust_gamma(-GAMMA_STEP);
+        }
+
+        if display_settings != previous_display_settings
+            && let Err(error) = display_settings.save(Path::new(DISPLAY_SETTINGS_PATH))
+        {
+            eprintln!("Could not save display settings: {error}");
+        }
+
+        let screenshot_requested = handle.is_key_pressed(KeyboardKey::KEY_F12);
+
+        game_of_life.render_to_framebuffer(
+            &mut framebuffer,
+            MATRIX_CELL_SCALLING_FACTOR,
+            Color::GOLD,
+            Color::DARKBLUE,
+        );
+
         //let mouse_position: raylib::prelude::Vector2 = handle.get_mouse_position();
         //let rectangle = Rectangle::new(85.0, 70.0, 250.0, 100.0);
 
-        let texture = handle
+        let mut texture = handle
             .load_texture_from_image(&raylib_thread, &framebuffer.color_buffer)
             .expect("The texture loaded from the color buffer should be valid");
 
+        if !display_settings.is_neutral() {
+            texture
+                .update_texture(&framebuffer.adjusted_pixels(&display_settings.lookup_table()))
+                .expect("The adjusted pixels should match the texture size");
+        }
+
         let mut draw_handle = handle.begin_drawing(&raylib_thread);
         {
-            //draw_handle.clear_background(Color::WHITE);
+            draw_handle.clear_background(Color::WHITE);
 
             draw_handle.draw_texture(&texture, 0, 0, Color::LIGHTGRAY);
 
@@ -116,13 +198,60 @@ fn game_loop() {
 
             //draw_handle.gui_button(rectangle, "Hello Word");
         }
+        drop(draw_handle);
 
-        game_of_life = game_of_life.calculate_next_generation();
+        if screenshot_requested {
+            handle.take_screenshot(&raylib_thread, &screenshot_filename());
+        }
+
+        game_of_life.advance_generation();
 
         thread::sleep(Duration::from_millis(125));
     }
 }
 
+fn cap_window_to_monitor(handle: &mut RaylibHandle, size: (i32, i32)) {
+    let monitor = get_current_monitor();
+    let (width, height) = clamp_to_monitor(
+        size,
+        (get_monitor_width(monitor), get_monitor_height(monitor)),
+    );
+
+    if (width, height) != size {
+        eprintln!(
+            "Window size {}x{} does not fit the monitor, capping it to {width}x{height}",
+            size.0, size.1
+        );
+    }
+
+    handle.set_window_size(width, height);
+}
+
+// raylib does not expose the monitor's video modes, so a fullscreen resolution
+// other than the monitor's own can only be flagged, not checked.
+fn warn_about_fullscreen_resolution(resolution: Option<(i32, i32)>) {
+    let monitor = get_current_monitor();
+    let monitor_size = (get_monitor_width(monitor), get_monitor_height(monitor));
+
+    if let Some((width, height)) = resolution
+        && (width, height) != monitor_size
+    {
+        eprintln!(
+            "Fullscreen resolution {width}x{height} differs from the monitor's {}x{}; raylib will switch to the closest video mode and may letterbox the image",
+            monitor_size.0, monitor_size.1
+        );
+    }
+}
+
+fn screenshot_filename() -> String {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("The system clock should be set after the Unix epoch")
+        .as_millis();
+
+    format!("screenshot_{timestamp}.png")
+}
+
 /*fn test_matrix() {
     let mut m = Matrix::new(MATRIX_WIDTH, MATRIX_HEIGHT);
 
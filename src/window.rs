@@ -0,0 +1,214 @@
+use crate::display::MAX_BRIGHTNESS;
+use crate::display::MAX_GAMMA;
+use crate::display::MIN_GAMMA;
+use crate::display::parse_brightness;
+use crate::display::parse_gamma;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum WindowMode {
+    Windowed,
+    Borderless,
+    Fullscreen,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct WindowConfig {
+    pub mode: WindowMode,
+    pub resolution: Option<(i32, i32)>,
+    pub brightness: Option<i32>,
+    pub gamma: Option<f32>,
+}
+
+impl WindowConfig {
+    pub fn new(mode: WindowMode, resolution: Option<(i32, i32)>) -> Self {
+        WindowConfig {
+            mode,
+            resolution,
+            brightness: None,
+            gamma: None,
+        }
+    }
+
+    pub fn from_args(args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut config = WindowConfig::new(WindowMode::Borderless, None);
+        let mut args = args.skip(1);
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--windowed" => config.mode = WindowMode::Windowed,
+                "--borderless" => config.mode = WindowMode::Borderless,
+                "--fullscreen" => config.mode = WindowMode::Fullscreen,
+                "--resolution" => {
+                    let value = args.next().ok_or_else(|| {
+                        String::from("--resolution expects a value like 1280x720")
+                    })?;
+                    config.resolution = Some(parse_resolution(&value)?);
+                }
+                "--brightness" => {
+                    let value = args.next().ok_or_else(|| {
+                        format!("--brightness expects a value between -{MAX_BRIGHTNESS} and {MAX_BRIGHTNESS}")
+                    })?;
+                    config.brightness = Some(parse_brightness(&value)?);
+                }
+                "--gamma" => {
+                    let value = args.next().ok_or_else(|| {
+                        format!("--gamma expects a value between {MIN_GAMMA} and {MAX_GAMMA}")
+                    })?;
+                    config.gamma = Some(parse_gamma(&value)?);
+                }
+                _ => return Err(format!("unknown argument `{arg}`")),
+            }
+        }
+
+        Ok(config)
+    }
+
+    // A size of 0x0 makes raylib open the fullscreen window at the monitor's
+    // own video mode instead of searching for the closest one to ours.
+    pub fn requested_size(&self, default_width: i32, default_height: i32) -> (i32, i32) {
+        match (self.resolution, self.mode) {
+            (None, WindowMode::Fullscreen) => (0, 0),
+            (_, _) => self.windowed_size(default_width, default_height),
+        }
+    }
+
+    pub fn windowed_size(&self, default_width: i32, default_height: i32) -> (i32, i32) {
+        self.resolution.unwrap_or((default_width, default_height))
+    }
+}
+
+pub fn clamp_to_monitor(size: (i32, i32), monitor_size: (i32, i32)) -> (i32, i32) {
+    let (width, height) = size;
+    let (monitor_width, monitor_height) = monitor_size;
+
+    if monitor_width <= 0 || monitor_height <= 0 {
+        return size;
+    }
+
+    (width.min(monitor_width), height.min(monitor_height))
+}
+
+fn parse_resolution(value: &str) -> Result<(i32, i32), String> {
+    let invalid = || format!("invalid resolution `{value}`, expected WIDTHxHEIGHT");
+
+    let (width, height) = value.split_once('x').ok_or_else(invalid)?;
+    let width: i32 = width.parse().map_err(|_| invalid())?;
+    let height: i32 = height.parse().map_err(|_| invalid())?;
+
+    if width <= 0 || height <= 0 {
+        return Err(invalid());
+    }
+
+    Ok((width, height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<WindowConfig, String> {
+        WindowConfig::from_args(
+            std::iter::once("game_of_life")
+                .chain(args.iter().copied())
+                .map(String::from),
+        )
+    }
+
+    #[test]
+    fn defaults_to_borderless_without_resolution() {
+        assert_eq!(
+            parse(&[]),
+            Ok(WindowConfig::new(WindowMode::Borderless, None))
+        );
+    }
+
+    #[test]
+    fn parses_mode_and_resolution() {
+        assert_eq!(
+            parse(&["--windowed", "--resolution", "1280x720"]),
+            Ok(WindowConfig::new(WindowMode::Windowed, Some((1280, 720))))
+        );
+    }
+
+    #[test]
+    fn rejects_missing_resolution_value() {
+        assert!(parse(&["--resolution"]).is_err());
+    }
+
+    #[test]
+    fn rejects_zero_sized_resolution() {
+        assert!(parse(&["--resolution", "0x720"]).is_err());
+    }
+
+    #[test]
+    fn rejects_resolution_without_height() {
+        assert!(parse(&["--resolution", "1280x"]).is_err());
+    }
+
+    #[test]
+    fn rejects_resolution_without_separator() {
+        assert!(parse(&["--resolution", "abc"]).is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_flag() {
+        assert_eq!(
+            parse(&["--foo"]),
+            Err(String::from("unknown argument `--foo`"))
+        );
+    }
+
+    #[test]
+    fn fullscreen_defaults_to_monitor_size() {
+        let config = WindowConfig::new(WindowMode::Fullscreen, None);
+        assert_eq!(config.requested_size(1900, 1000), (0, 0));
+
+        let config = WindowConfig::new(WindowMode::Fullscreen, Some((1280, 720)));
+        assert_eq!(config.requested_size(1900, 1000), (1280, 720));
+
+        let config = WindowConfig::new(WindowMode::Windowed, None);
+        assert_eq!(config.requested_size(1900, 1000), (1900, 1000));
+    }
+
+    #[test]
+    fn windowed_size_ignores_fullscreen_default() {
+        let config = WindowConfig::new(WindowMode::Fullscreen, None);
+        assert_eq!(config.windowed_size(1900, 1000), (1900, 1000));
+
+        let config = WindowConfig::new(WindowMode::Fullscreen, Some((1280, 720)));
+        assert_eq!(config.windowed_size(1900, 1000), (1280, 720));
+    }
+
+    #[test]
+    fn clamps_oversized_resolution_to_monitor() {
+        assert_eq!(clamp_to_monitor((1900, 1000), (1600, 900)), (1600, 900));
+        assert_eq!(clamp_to_monitor((1280, 1000), (1600, 900)), (1280, 900));
+        assert_eq!(clamp_to_monitor((1280, 720), (1600, 900)), (1280, 720));
+        assert_eq!(clamp_to_monitor((1280, 720), (0, 0)), (1280, 720));
+    }
+
+    #[test]
+    fn parses_display_overrides() {
+        assert_eq!(
+            parse(&[]).map(|config| (config.brightness, config.gamma)),
+            Ok((None, None))
+        );
+        assert_eq!(
+            parse(&["--brightness", "-30", "--gamma", "1.5"])
+                .map(|config| (config.brightness, config.gamma)),
+            Ok((Some(-30), Some(1.5)))
+        );
+        assert_eq!(
+            parse(&["--brightness", "500"]).map(|config| config.brightness),
+            Ok(Some(MAX_BRIGHTNESS))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_display_overrides() {
+        assert!(parse(&["--brightness"]).is_err());
+        assert!(parse(&["--brightness", "bright"]).is_err());
+        assert!(parse(&["--gamma"]).is_err());
+        assert!(parse(&["--gamma", "dark"]).is_err());
+    }
+}